@@ -0,0 +1,302 @@
+use std::ops::Range;
+
+use regex::Regex;
+
+use super::markers::Marker;
+
+/// Something that can locate occurrences of a single [`Marker`] (or family of
+/// markers) in raw issue text.
+///
+/// Implementations report byte-offset ranges into the text they were given,
+/// matching the conventions of [`str::find`]/the `regex` crate rather than
+/// character indices, so callers can slice `text` directly with the returned
+/// `Range`.
+pub trait MarkerDetector {
+    fn detect(&self, text: &str) -> Vec<(Marker, Range<usize>)>;
+}
+
+/// A [`MarkerDetector`] backed by a single compiled regular expression.
+///
+/// This covers every built-in detector below: each one just tags every match
+/// of its regex (or, when `group` is non-zero, a specific capture group
+/// within each match) with a fixed [`Marker`]. A non-zero `group` is how a
+/// pattern anchors on context (e.g. "preceded by whitespace") without that
+/// context ending up inside the reported span.
+struct RegexDetector {
+    marker: Marker,
+    pattern: Regex,
+    group: usize,
+}
+
+impl RegexDetector {
+    fn new(marker: Marker, pattern: &str) -> Self {
+        Self::with_group(marker, pattern, 0)
+    }
+
+    fn with_group(marker: Marker, pattern: &str, group: usize) -> Self {
+        Self {
+            marker,
+            pattern: Regex::new(pattern).expect("built-in detector pattern must compile"),
+            group,
+        }
+    }
+}
+
+impl MarkerDetector for RegexDetector {
+    fn detect(&self, text: &str) -> Vec<(Marker, Range<usize>)> {
+        self.pattern
+            .captures_iter(text)
+            .filter_map(|captures| captures.get(self.group))
+            .map(|m| (self.marker.clone(), m.range()))
+            .collect()
+    }
+}
+
+/// Built-in detectors for the structurally regular markers, in priority
+/// order (highest priority first). Priority only matters as a tie-breaker
+/// between equal-length overlapping matches; see [`DetectorRegistry::annotate`].
+fn built_in_detectors() -> Vec<Box<dyn MarkerDetector>> {
+    vec![
+        // Fenced blocks are checked before the things that can appear inside
+        // them, so e.g. a fenced ``` block wrapping a URL is reported as a
+        // single `StructuredCodeBlock` (and a `{noformat}` block as a single
+        // `NoFormatBlock`) rather than a `WebLink`.
+        Box::new(RegexDetector::new(
+            Marker::StructuredCodeBlock,
+            r"(?s)```.*?```",
+        )),
+        Box::new(RegexDetector::new(
+            Marker::NoFormatBlock,
+            r"(?s)\{noformat\}.*?\{noformat\}",
+        )),
+        Box::new(RegexDetector::new(
+            Marker::Traceback,
+            r"(?m)^Traceback \(most recent call last\):(\n(?:\s+.*|\S.*Error.*))*",
+        )),
+        Box::new(RegexDetector::new(
+            Marker::Log,
+            r"(?m)^\s*\d{4}-\d{2}-\d{2}[ T]\d{2}:\d{2}:\d{2}.*\b(TRACE|DEBUG|INFO|WARN|ERROR|FATAL)\b.*$",
+        )),
+        Box::new(RegexDetector::new(
+            Marker::GithubLink,
+            r"https?://(www\.)?github\.com/\S+",
+        )),
+        Box::new(RegexDetector::new(
+            Marker::IssueLink,
+            r"\b[A-Z][A-Z0-9]+-\d+\b",
+        )),
+        Box::new(RegexDetector::new(
+            Marker::UserProfileLink,
+            r"https?://\S+/(?:users?|~)/\S+",
+        )),
+        Box::new(RegexDetector::new(Marker::WebLink, r"https?://\S+")),
+        Box::new(RegexDetector::new(
+            Marker::IPAddress,
+            r"\b(?:\d{1,3}\.){3}\d{1,3}\b",
+        )),
+        Box::new(RegexDetector::new(
+            Marker::StorageSize,
+            r"(?i)\b\d+(\.\d+)?\s?(B|KB|MB|GB|TB)\b",
+        )),
+        Box::new(RegexDetector::new(
+            Marker::VersionNumber,
+            r"\b\d+(\.\d+){1,}\b",
+        )),
+        Box::new(RegexDetector::new(
+            Marker::Date,
+            r"\b\d{4}-\d{2}-\d{2}\b",
+        )),
+        // Anchored on "not preceded by a word character" (captured via a
+        // group, so the boundary char itself isn't reported as part of the
+        // file path) rather than requiring whitespace specifically, so
+        // paths after punctuation like `(/path)`, `path:/path`, or `"/path"`
+        // still match. Without this anchor at all, prose like "and/or"
+        // would be mis-annotated as the path "/or".
+        Box::new(RegexDetector::with_group(
+            Marker::FilePath,
+            r"(?:^|[^\w])([A-Za-z]:\\[\w.\-\\/]*\w|\.{0,2}/[\w.\-\\/]*\w)",
+            1,
+        )),
+    ]
+}
+
+/// A configurable set of [`MarkerDetector`]s that can be run over a document
+/// as a whole, producing non-overlapping annotations.
+///
+/// Detectors are tried in registration order; when two detectors produce
+/// overlapping matches, the longer match wins, and ties are broken in favour
+/// of whichever detector was registered first.
+pub struct DetectorRegistry {
+    detectors: Vec<Box<dyn MarkerDetector>>,
+}
+
+impl DetectorRegistry {
+    pub fn new() -> Self {
+        Self {
+            detectors: Vec::new(),
+        }
+    }
+
+    /// A registry pre-loaded with the built-in detectors for the
+    /// structurally regular markers (links, version numbers, file paths,
+    /// tracebacks, ...).
+    pub fn with_builtin_detectors() -> Self {
+        Self {
+            detectors: built_in_detectors(),
+        }
+    }
+
+    pub fn register(&mut self, detector: Box<dyn MarkerDetector>) -> &mut Self {
+        self.detectors.push(detector);
+        self
+    }
+
+    /// Run every registered detector over `text` and resolve overlaps into a
+    /// single, non-overlapping, left-to-right list of annotations.
+    pub fn annotate(&self, text: &str) -> Vec<(Marker, Range<usize>)> {
+        let mut candidates: Vec<(Marker, Range<usize>, usize)> = Vec::new();
+        for (priority, detector) in self.detectors.iter().enumerate() {
+            for (marker, range) in detector.detect(text) {
+                candidates.push((marker, range, priority));
+            }
+        }
+
+        // Longest match first, ties broken by earlier registration (lower
+        // priority index), then by start offset for determinism.
+        candidates.sort_by(|a, b| {
+            let len_a = a.1.end - a.1.start;
+            let len_b = b.1.end - b.1.start;
+            len_b
+                .cmp(&len_a)
+                .then(a.2.cmp(&b.2))
+                .then(a.1.start.cmp(&b.1.start))
+        });
+
+        let mut accepted: Vec<(Marker, Range<usize>)> = Vec::new();
+        for (marker, range, _) in candidates {
+            let overlaps = accepted
+                .iter()
+                .any(|(_, kept)| range.start < kept.end && kept.start < range.end);
+            if !overlaps {
+                accepted.push((marker, range));
+            }
+        }
+
+        accepted.sort_by_key(|(_, range)| range.start);
+        accepted
+    }
+}
+
+impl Default for DetectorRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn structured_code_block_wins_over_a_nested_weblink() {
+        let registry = DetectorRegistry::with_builtin_detectors();
+        let text = "See ```https://example.com/snippet``` for the repro.";
+
+        let annotations = registry.annotate(text);
+
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(annotations[0].0, Marker::StructuredCodeBlock);
+        assert_eq!(&text[annotations[0].1.clone()], "```https://example.com/snippet```");
+    }
+
+    #[test]
+    fn equal_length_overlap_breaks_ties_by_registration_order() {
+        let registry = DetectorRegistry::with_builtin_detectors();
+        let text = "https://github.com/foo/bar";
+
+        let annotations = registry.annotate(text);
+
+        // GithubLink and WebLink both match the exact same span here;
+        // GithubLink is registered first, so it wins the tie.
+        assert_eq!(annotations.len(), 1);
+        assert_eq!(annotations[0].0, Marker::GithubLink);
+        assert_eq!(annotations[0].1, 0..text.len());
+    }
+
+    #[test]
+    fn annotate_output_is_non_overlapping_and_start_sorted() {
+        let registry = DetectorRegistry::with_builtin_detectors();
+        let text = "Seen on 1.2.3 at 10.0.0.1, see https://example.com for the fix.";
+
+        let annotations = registry.annotate(text);
+
+        assert!(annotations.len() >= 2);
+        for window in annotations.windows(2) {
+            let (_, a) = &window[0];
+            let (_, b) = &window[1];
+            assert!(a.start <= b.start, "annotations must be start-sorted");
+            assert!(a.end <= b.start, "annotations must not overlap");
+        }
+    }
+
+    #[test]
+    fn file_path_detector_does_not_fire_on_ordinary_slash_separated_prose() {
+        let registry = DetectorRegistry::with_builtin_detectors();
+        let text = "Works on Linux and/or macOS.";
+
+        let annotations = registry.annotate(text);
+
+        assert!(
+            annotations.iter().all(|(marker, _)| *marker != Marker::FilePath),
+            "expected no FilePath annotation, got {annotations:?}"
+        );
+    }
+
+    #[test]
+    fn file_path_detector_fires_on_an_actual_path() {
+        let registry = DetectorRegistry::with_builtin_detectors();
+        let text = "fails in /usr/local/bin/tool when run";
+
+        let annotations = registry.annotate(text);
+
+        let file_paths: Vec<_> = annotations
+            .iter()
+            .filter(|(marker, _)| *marker == Marker::FilePath)
+            .collect();
+        assert_eq!(file_paths.len(), 1);
+        assert_eq!(&text[file_paths[0].1.clone()], "/usr/local/bin/tool");
+    }
+
+    #[test]
+    fn file_path_detector_fires_after_punctuation_not_just_whitespace() {
+        let registry = DetectorRegistry::with_builtin_detectors();
+        for text in [
+            "see (/usr/local/bin/tool) for details",
+            "path:/usr/local/bin/tool is broken",
+            "error in \"/usr/local/bin/tool\"",
+        ] {
+            let annotations = registry.annotate(text);
+            let file_paths: Vec<_> = annotations
+                .iter()
+                .filter(|(marker, _)| *marker == Marker::FilePath)
+                .collect();
+            assert_eq!(file_paths.len(), 1, "expected a FilePath match in {text:?}");
+            assert_eq!(&text[file_paths[0].1.clone()], "/usr/local/bin/tool");
+        }
+    }
+
+    #[test]
+    fn file_path_detector_keeps_a_windows_path_with_a_trailing_slash_segment_intact() {
+        let registry = DetectorRegistry::with_builtin_detectors();
+        let text = r"seen in C:\Users\foo/bar.txt during the crash";
+
+        let annotations = registry.annotate(text);
+
+        let file_paths: Vec<_> = annotations
+            .iter()
+            .filter(|(marker, _)| *marker == Marker::FilePath)
+            .collect();
+        assert_eq!(file_paths.len(), 1);
+        assert_eq!(&text[file_paths[0].1.clone()], r"C:\Users\foo/bar.txt");
+    }
+}
@@ -0,0 +1,105 @@
+use std::ops::Range;
+
+use serde::{Deserialize, Serialize};
+
+use super::markers::Marker;
+
+/// One detected marker span within an [`AnnotatedDocument`].
+///
+/// # JSON schema
+///
+/// ```json
+/// {"marker": "WEBLINK", "start": 10, "end": 42}
+/// ```
+///
+/// `marker` is the canonical [`Marker::string_marker`] token, not the Rust
+/// variant name. `start`/`end` are byte offsets into the owning document's
+/// `text`, matching the ranges produced by
+/// [`DetectorRegistry::annotate`](super::detector::DetectorRegistry::annotate).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub marker: Marker,
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Annotation {
+    pub fn span(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+/// A piece of issue text together with the marker spans detected in it.
+///
+/// This is the unit of exchange between the Rust detection engine and the
+/// Python/ML pipelines that consume its output: it can be persisted as JSON
+/// and re-loaded later without re-running detection.
+///
+/// # JSON schema
+///
+/// ```json
+/// {
+///   "text": "See https://example.com/issues/1 for details.",
+///   "annotations": [
+///     {"marker": "WEBLINK", "start": 4, "end": 32}
+///   ]
+/// }
+/// ```
+///
+/// Unrecognised `marker` tokens (from a dataset annotated with an older or
+/// newer marker vocabulary) deserialize into [`Marker::Unknown`] rather than
+/// failing the whole document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AnnotatedDocument {
+    pub text: String,
+    pub annotations: Vec<Annotation>,
+}
+
+impl AnnotatedDocument {
+    pub fn new(text: String, annotations: Vec<(Marker, Range<usize>)>) -> Self {
+        Self {
+            text,
+            annotations: annotations
+                .into_iter()
+                .map(|(marker, range)| Annotation {
+                    marker,
+                    start: range.start,
+                    end: range.end,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn annotated_document_round_trips_through_json() {
+        let doc = AnnotatedDocument::new(
+            "See https://example.com for details.".to_string(),
+            vec![(Marker::WebLink, 4..23)],
+        );
+
+        let json = serde_json::to_string(&doc).unwrap();
+        assert!(json.contains("\"WEBLINK\""));
+
+        let parsed: AnnotatedDocument = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, doc);
+    }
+
+    #[test]
+    fn unknown_marker_tokens_deserialize_as_fallback_instead_of_failing() {
+        let json = r#"{
+            "text": "legacy dataset",
+            "annotations": [{"marker": "SOME_RETIRED_MARKER", "start": 0, "end": 6}]
+        }"#;
+
+        let doc: AnnotatedDocument = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            doc.annotations[0].marker,
+            Marker::Unknown("SOME_RETIRED_MARKER".to_string())
+        );
+    }
+}
@@ -1,3 +1,6 @@
+use serde::Deserialize;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Marker {
     Attachment,
     ClassName,
@@ -25,15 +28,114 @@ pub enum Marker {
     UnformattedTraceback,
     UserProfileLink,
     VersionNumber,
-    WebLink
+    WebLink,
+    /// A token that does not match any known marker, preserved verbatim so
+    /// that datasets produced by an older or newer marker vocabulary can
+    /// still be loaded. Never produced by [`DetectorRegistry`](super::detector::DetectorRegistry);
+    /// only ever constructed while deserializing.
+    Unknown(String)
+}
+
+/// The family a fine-grained [`Marker`] variant belongs to, used by
+/// [`Marker::coarsen`] to collapse detail a caller doesn't care about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MarkerFamily {
+    Traceback,
+    Log,
+    ClassName,
+    MethodOrVariableName,
+}
+
+/// An error returned when a string does not match any [`Marker::string_marker`]
+/// token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseMarkerError(String);
+
+impl std::fmt::Display for ParseMarkerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "'{}' is not a known marker token", self.0)
+    }
 }
 
+impl std::error::Error for ParseMarkerError {}
+
 impl Marker {
     pub fn all_markers() -> Vec<Marker> {
-        vec![]
+        vec![
+            Self::Attachment,
+            Self::ClassName,
+            Self::CloudInstanceSpec,
+            Self::Date,
+            Self::FilePath,
+            Self::FormattedLogging,
+            Self::FormattedTraceback,
+            Self::GithubLink,
+            Self::ImageAttachment,
+            Self::InlineCode,
+            Self::IssueLink,
+            Self::IPAddress,
+            Self::Log,
+            Self::MethodOrVariableName,
+            Self::NoFormatBlock,
+            Self::PackageName,
+            Self::SimpleClassName,
+            Self::SimpleMethodOrVariableName,
+            Self::StorageSize,
+            Self::StructuredCodeBlock,
+            Self::TechnologyName,
+            Self::Traceback,
+            Self::UnformattedLog,
+            Self::UnformattedTraceback,
+            Self::UserProfileLink,
+            Self::VersionNumber,
+            Self::WebLink,
+        ]
+    }
+
+    /// Parse the exact token produced by [`Marker::string_marker`] back into
+    /// a [`Marker`].
+    pub fn try_from_string_marker(token: &str) -> Result<Marker, ParseMarkerError> {
+        Self::all_markers()
+            .into_iter()
+            .find(|marker| marker.string_marker() == token)
+            .ok_or_else(|| ParseMarkerError(token.to_string()))
+    }
+
+    /// The family this variant belongs to, for markers that come in a
+    /// fine-grained/coarse-grained pair (e.g. `Traceback` vs.
+    /// `FormattedTraceback`/`UnformattedTraceback`). Returns `None` for
+    /// markers that have no siblings.
+    pub fn family(&self) -> Option<MarkerFamily> {
+        match self {
+            Self::Traceback | Self::FormattedTraceback | Self::UnformattedTraceback => {
+                Some(MarkerFamily::Traceback)
+            }
+            Self::Log | Self::FormattedLogging | Self::UnformattedLog => Some(MarkerFamily::Log),
+            Self::ClassName | Self::SimpleClassName => Some(MarkerFamily::ClassName),
+            Self::MethodOrVariableName | Self::SimpleMethodOrVariableName => {
+                Some(MarkerFamily::MethodOrVariableName)
+            }
+            _ => None,
+        }
+    }
+
+    /// Map a fine-grained variant to the representative [`Marker`] of its
+    /// [`family`](Marker::family), so callers can choose annotation
+    /// granularity. Markers with no family map to themselves.
+    pub fn coarsen(&self) -> Marker {
+        match self.family() {
+            Some(MarkerFamily::Traceback) => Self::Traceback,
+            Some(MarkerFamily::Log) => Self::Log,
+            Some(MarkerFamily::ClassName) => Self::ClassName,
+            Some(MarkerFamily::MethodOrVariableName) => Self::MethodOrVariableName,
+            None => self.clone(),
+        }
     }
 
     pub fn string_marker(&self) -> String {
+        if let Self::Unknown(token) = self {
+            return token.clone();
+        }
         match self {
             Self::Attachment => "ATTACHMENT",
             Self::ClassName => "CLASSNAME",
@@ -61,7 +163,100 @@ impl Marker {
             Self::UnformattedTraceback => "UNFORMATTEDTRACEBACK",
             Self::UserProfileLink => "USERPROFILELINK",
             Self::VersionNumber => "VERSIONNUMBER",
-            Self::WebLink => "WEBLINK"
+            Self::WebLink => "WEBLINK",
+            Self::Unknown(_) => unreachable!("handled by the early return above"),
         }.into()
     }
+}
+
+impl std::str::FromStr for Marker {
+    type Err = ParseMarkerError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        Self::try_from_string_marker(token)
+    }
+}
+
+/// Serializes as the canonical [`Marker::string_marker`] token (e.g.
+/// `"WEBLINK"`, `"LLLOG"`) rather than the Rust variant name, so annotation
+/// exports stay stable across refactors of this enum.
+impl serde::Serialize for Marker {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.string_marker())
+    }
+}
+
+/// Deserializes from a [`Marker::string_marker`] token. Tokens that don't
+/// match any known marker fall back to [`Marker::Unknown`] rather than
+/// failing, so datasets annotated with an older or newer marker vocabulary
+/// still load.
+impl<'de> serde::Deserialize<'de> for Marker {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let token = String::deserialize(deserializer)?;
+        Ok(Self::try_from_string_marker(&token).unwrap_or(Self::Unknown(token)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_markers_round_trip_through_string_marker() {
+        for marker in Marker::all_markers() {
+            let token = marker.string_marker();
+            assert_eq!(
+                token.parse::<Marker>().unwrap_or_else(|e| panic!("{e}")),
+                marker,
+                "round-trip failed for token {token:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn irregular_tokens_parse() {
+        assert_eq!("LLLOG".parse::<Marker>().unwrap(), Marker::Log);
+        assert_eq!(
+            "TTTRACEBACK".parse::<Marker>().unwrap(),
+            Marker::Traceback
+        );
+        assert_eq!(
+            "IP ADDRESS".parse::<Marker>().unwrap(),
+            Marker::IPAddress
+        );
+    }
+
+    #[test]
+    fn unknown_token_is_an_error() {
+        assert!("NOT_A_MARKER".parse::<Marker>().is_err());
+    }
+
+    #[test]
+    fn coarsen_maps_to_family_representative() {
+        assert_eq!(Marker::FormattedTraceback.coarsen(), Marker::Traceback);
+        assert_eq!(Marker::UnformattedLog.coarsen(), Marker::Log);
+        assert_eq!(Marker::SimpleClassName.coarsen(), Marker::ClassName);
+        assert_eq!(Marker::Date.coarsen(), Marker::Date);
+    }
+
+    #[test]
+    fn serde_round_trips_known_markers_through_their_string_marker() {
+        for marker in Marker::all_markers() {
+            let json = serde_json::to_string(&marker).unwrap();
+            assert_eq!(json, format!("{:?}", marker.string_marker()));
+            assert_eq!(serde_json::from_str::<Marker>(&json).unwrap(), marker);
+        }
+    }
+
+    #[test]
+    fn serde_falls_back_to_unknown_for_unrecognised_tokens() {
+        let marker: Marker = serde_json::from_str("\"SOME_LEGACY_TOKEN\"").unwrap();
+        assert_eq!(marker, Marker::Unknown("SOME_LEGACY_TOKEN".to_string()));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,152 @@
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+
+use super::markers::Marker;
+
+/// A record of one span that [`mask`] replaced with a marker token, enough
+/// to splice the original text back in with [`unmask`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Replacement {
+    pub marker: Marker,
+    /// The byte span the replaced text occupied in the original document.
+    pub original_span: Range<usize>,
+    /// The byte span the marker token occupies in the masked output.
+    pub masked_span: Range<usize>,
+    /// The original substring, so [`unmask`] does not need the source text.
+    pub original_text: String,
+}
+
+/// Replace every annotated span in `text` with its [`Marker::string_marker`]
+/// token, returning the masked text alongside a side-table that records
+/// enough information to undo the substitution with [`unmask`].
+///
+/// `annotations` must be non-overlapping; `DetectorRegistry::annotate`
+/// already guarantees this. Markers in `keep_distinct` are suffixed with a
+/// `_1`, `_2`, ... index per occurrence (in document order) instead of all
+/// collapsing onto the same token. The index counts occurrences, not
+/// distinct original strings: the same class name mentioned twice gets two
+/// different indices.
+pub fn mask(
+    text: &str,
+    annotations: &[(Marker, Range<usize>)],
+    keep_distinct: &HashSet<Marker>,
+) -> (String, Vec<Replacement>) {
+    let mut sorted: Vec<&(Marker, Range<usize>)> = annotations.iter().collect();
+    sorted.sort_by_key(|(_, range)| range.start);
+
+    let mut output = String::with_capacity(text.len());
+    let mut replacements = Vec::with_capacity(sorted.len());
+    let mut counts: HashMap<Marker, usize> = HashMap::new();
+    let mut cursor = 0;
+
+    for (marker, range) in sorted {
+        output.push_str(&text[cursor..range.start]);
+
+        let mut token = marker.string_marker();
+        if keep_distinct.contains(marker) {
+            let count = counts.entry(marker.clone()).or_insert(0);
+            *count += 1;
+            token = format!("{token}_{count}");
+        }
+
+        let masked_start = output.len();
+        output.push_str(&token);
+        let masked_span = masked_start..output.len();
+
+        replacements.push(Replacement {
+            marker: marker.clone(),
+            original_span: range.clone(),
+            masked_span,
+            original_text: text[range.clone()].to_string(),
+        });
+
+        cursor = range.end;
+    }
+    output.push_str(&text[cursor..]);
+
+    (output, replacements)
+}
+
+/// Undo a [`mask`] pass, splicing the original substrings back into the
+/// positions recorded by `replacements`.
+pub fn unmask(masked: &str, replacements: &[Replacement]) -> String {
+    let mut sorted: Vec<&Replacement> = replacements.iter().collect();
+    sorted.sort_by_key(|r| r.masked_span.start);
+
+    let mut output = String::with_capacity(masked.len());
+    let mut cursor = 0;
+    for replacement in sorted {
+        output.push_str(&masked[cursor..replacement.masked_span.start]);
+        output.push_str(&replacement.original_text);
+        cursor = replacement.masked_span.end;
+    }
+    output.push_str(&masked[cursor..]);
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_annotations_pass_through_unchanged() {
+        let text = "nothing to see here";
+        let (masked, reps) = mask(text, &[], &HashSet::new());
+        assert_eq!(masked, text);
+        assert!(reps.is_empty());
+        assert_eq!(unmask(&masked, &reps), text);
+    }
+
+    #[test]
+    fn round_trips_when_token_is_longer_than_original_span() {
+        // "IP ADDRESS" (10 bytes) is longer than "1.1" (3 bytes).
+        let text = "ping 1.1 now";
+        let anns = vec![(Marker::IPAddress, 5..8)];
+        let (masked, reps) = mask(text, &anns, &HashSet::new());
+        assert_eq!(masked, "ping IP ADDRESS now");
+        assert_eq!(unmask(&masked, &reps), text);
+    }
+
+    #[test]
+    fn round_trips_when_token_is_shorter_than_original_span() {
+        // "WEBLINK" (7 bytes) is shorter than the URL it replaces.
+        let text = "see https://example.com/issues/1 for details";
+        let anns = vec![(Marker::WebLink, 4..32)];
+        let (masked, reps) = mask(text, &anns, &HashSet::new());
+        assert_eq!(masked, "see WEBLINK for details");
+        assert_eq!(unmask(&masked, &reps), text);
+    }
+
+    #[test]
+    fn round_trips_with_multiple_annotations_of_mixed_length() {
+        let text = "Foo at 1.1 and https://a.b both fail";
+        let anns = vec![(Marker::IPAddress, 7..10), (Marker::WebLink, 15..26)];
+        let (masked, reps) = mask(text, &anns, &HashSet::new());
+        assert_eq!(reps.len(), 2);
+        assert_eq!(unmask(&masked, &reps), text);
+    }
+
+    #[test]
+    fn keep_distinct_suffixes_tokens_with_a_per_marker_index() {
+        let text = "Foo threw, then Bar threw, then Foo threw again";
+        let anns = vec![
+            (Marker::ClassName, 0..3),
+            (Marker::ClassName, 16..19),
+            (Marker::ClassName, 32..35),
+        ];
+        let mut keep_distinct = HashSet::new();
+        keep_distinct.insert(Marker::ClassName);
+
+        let (masked, reps) = mask(text, &anns, &keep_distinct);
+
+        assert_eq!(
+            masked,
+            "CLASSNAME_1 threw, then CLASSNAME_2 threw, then CLASSNAME_3 threw again"
+        );
+        assert_eq!(reps[0].original_text, "Foo");
+        assert_eq!(reps[1].original_text, "Bar");
+        assert_eq!(reps[2].original_text, "Foo");
+        assert_eq!(unmask(&masked, &reps), text);
+    }
+}
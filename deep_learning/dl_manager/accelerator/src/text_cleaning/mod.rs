@@ -0,0 +1,9 @@
+pub mod detector;
+pub mod document;
+pub mod mask;
+pub mod markers;
+
+pub use detector::{DetectorRegistry, MarkerDetector};
+pub use document::{AnnotatedDocument, Annotation};
+pub use mask::{mask, unmask, Replacement};
+pub use markers::Marker;